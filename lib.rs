@@ -10,6 +10,14 @@ mod my_contract {
     #[ink(storage)]
     pub struct MyContract {
         registrations: Mapping<AccountId, bool>,
+        nonces: Mapping<AccountId, u64>,
+        /// Binds signed payloads to this contract and chain, so a signature
+        /// valid here cannot be replayed against another deployment.
+        domain_separator: [u8; 32],
+        /// Accounts registered via an Ethereum (secp256k1) key, keyed by
+        /// their 20-byte Ethereum address.
+        eth_registrations: Mapping<[u8; 20], bool>,
+        eth_nonces: Mapping<[u8; 20], u64>,
     }
 
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -17,17 +25,51 @@ mod my_contract {
     pub enum Error {
         AlreadyRegistered,
         InvalidSignature,
+        RecoveryFailed,
+        BadNonce,
+    }
+
+    /// Emitted when an account successfully registers, so off-chain indexers
+    /// can subscribe instead of polling `is_registered`.
+    #[ink(event)]
+    pub struct Registered {
+        #[ink(topic)]
+        account: AccountId,
+        nonce: u64,
+    }
+
+    /// Emitted when an Ethereum address successfully registers, mirroring
+    /// `Registered` for the `register_with_eth_signature` entry point so
+    /// indexers don't need to poll `is_eth_registered`.
+    #[ink(event)]
+    pub struct EthRegistered {
+        #[ink(topic)]
+        address: [u8; 20],
+        nonce: u64,
     }
 
     impl MyContract {
         #[ink(constructor)]
-        pub fn new() -> Self {
-            Self { registrations: Mapping::default() }
+        pub fn new(chain_id: u64) -> Self {
+            let contract_id = Self::env().account_id();
+            let mut preimage = Vec::new();
+            preimage.extend_from_slice(&chain_id.to_le_bytes());
+            preimage.extend_from_slice(contract_id.as_ref());
+            let mut domain_separator = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Blake2x256>(&preimage, &mut domain_separator);
+
+            Self {
+                registrations: Mapping::default(),
+                nonces: Mapping::default(),
+                domain_separator,
+                eth_registrations: Mapping::default(),
+                eth_nonces: Mapping::default(),
+            }
         }
 
         #[ink(constructor)]
         pub fn default() -> Self {
-            Self::new()
+            Self::new(0)
         }
 
         /// Register the caller if they provide a valid signature for the message.
@@ -38,31 +80,57 @@ mod my_contract {
             signature: Vec<u8>,
         ) -> Result<(), Error> {
             let caller = self.env().caller();
-            if self.is_registered(caller) {
-                return Err(Error::AlreadyRegistered);
-            }
+            self.register_account(caller, message, signature)
+        }
 
-            // Hash the message
-            let mut output = <Blake2x256 as HashOutput>::Type::default();
-            ink::env::hash_bytes::<Blake2x256>(&message, &mut output);
+        /// Verifies and registers each `(account, message, signature)` entry
+        /// independently, so a relayer can submit many players' signed
+        /// registrations in one transaction. Entries that fail (already
+        /// registered, bad signature, stale nonce) are skipped; all others
+        /// are registered, applying the same verification and nonce-increment
+        /// logic as `register_with_signature`.
+        #[ink(message)]
+        pub fn register_batch(
+            &mut self,
+            entries: Vec<(AccountId, Vec<u8>, Vec<u8>)>,
+        ) -> Vec<Result<(), Error>> {
+            entries
+                .into_iter()
+                .map(|(account, message, signature)| {
+                    self.register_account(account, message, signature)
+                })
+                .collect()
+        }
 
-            // In the test environment, we can verify the signature directly
-            #[cfg(test)]
-            {
-                // For testing, we'll just check if the signature matches the message hash
-                if signature != output.to_vec() {
-                    return Err(Error::InvalidSignature);
-                }
+        /// Shared registration logic for `account`, used by both the
+        /// single-account and batch entry points.
+        fn register_account(
+            &mut self,
+            account: AccountId,
+            message: Vec<u8>,
+            signature: Vec<u8>,
+        ) -> Result<(), Error> {
+            if self.is_registered(account) {
+                return Err(Error::AlreadyRegistered);
             }
 
-            // In production, we would use the actual signature verification
-            #[cfg(not(test))]
-            {
-                // TODO: Implement actual signature verification
-                return Err(Error::InvalidSignature);
+            let nonce = self.nonce_of(account);
+            let mut expected_message = Vec::new();
+            expected_message.extend_from_slice(&self.domain_separator);
+            expected_message.extend_from_slice(self.env().account_id().as_ref());
+            expected_message.extend_from_slice(account.as_ref());
+            expected_message.extend_from_slice(&nonce.to_le_bytes());
+            if message != expected_message {
+                return Err(Error::BadNonce);
             }
 
-            self.registrations.insert(caller, &true);
+            Self::verify_signature(&signature, &message, &account)?;
+
+            self.registrations.insert(account, &true);
+            self.nonces.insert(account, &(nonce + 1));
+
+            self.env().emit_event(Registered { account, nonce });
+
             Ok(())
         }
 
@@ -71,6 +139,124 @@ mod my_contract {
         pub fn is_registered(&self, account: AccountId) -> bool {
             self.registrations.get(account).unwrap_or(false)
         }
+
+        /// Returns the next nonce expected from `account`'s registration signature.
+        #[ink(message)]
+        pub fn nonce_of(&self, account: AccountId) -> u64 {
+            self.nonces.get(account).unwrap_or(0)
+        }
+
+        /// Returns this deployment's domain separator, needed by callers to
+        /// construct the payload they must sign for registration.
+        #[ink(message)]
+        pub fn domain_separator(&self) -> [u8; 32] {
+            self.domain_separator
+        }
+
+        /// Registers the holder of an Ethereum (secp256k1) key, recovered
+        /// from `signature` over `message`. Mirrors `register_with_signature`:
+        /// `message` must be the nonce-bound payload for the recovered
+        /// Ethereum address, preventing replay across addresses and chains.
+        #[ink(message)]
+        pub fn register_with_eth_signature(
+            &mut self,
+            message: Vec<u8>,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            let mut message_hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Blake2x256>(&message, &mut message_hash);
+
+            let mut compressed_pubkey = [0u8; 33];
+            ink::env::ecdsa_recover(&signature, &message_hash, &mut compressed_pubkey)
+                .map_err(|_| Error::RecoveryFailed)?;
+
+            let mut eth_address = [0u8; 20];
+            ink::env::ecdsa_to_eth_address(&compressed_pubkey, &mut eth_address)
+                .map_err(|_| Error::RecoveryFailed)?;
+
+            if self.is_eth_registered(eth_address) {
+                return Err(Error::AlreadyRegistered);
+            }
+
+            let nonce = self.eth_nonce_of(eth_address);
+            let mut expected_message = Vec::new();
+            expected_message.extend_from_slice(&self.domain_separator);
+            expected_message.extend_from_slice(self.env().account_id().as_ref());
+            expected_message.extend_from_slice(&eth_address);
+            expected_message.extend_from_slice(&nonce.to_le_bytes());
+            if message != expected_message {
+                return Err(Error::BadNonce);
+            }
+
+            self.eth_registrations.insert(eth_address, &true);
+            self.eth_nonces.insert(eth_address, &(nonce + 1));
+
+            self.env().emit_event(EthRegistered {
+                address: eth_address,
+                nonce,
+            });
+
+            Ok(())
+        }
+
+        /// Checks if an Ethereum address is registered.
+        #[ink(message)]
+        pub fn is_eth_registered(&self, addr: [u8; 20]) -> bool {
+            self.eth_registrations.get(addr).unwrap_or(false)
+        }
+
+        /// Returns the next nonce expected from `addr`'s registration signature.
+        #[ink(message)]
+        pub fn eth_nonce_of(&self, addr: [u8; 20]) -> u64 {
+            self.eth_nonces.get(addr).unwrap_or(0)
+        }
+
+        /// Verifies that `signature` over `message` was produced by `caller`.
+        ///
+        /// A 64-byte signature is treated as sr25519 and checked directly
+        /// against the raw `message` (the caller's `AccountId` bytes serve
+        /// as the sr25519 public key). A 65-byte signature is treated as
+        /// ECDSA (secp256k1), which verifies against a 32-byte digest: the
+        /// message is hashed before being passed to `ecdsa_recover`, and the
+        /// recovered public key is hashed down to an `AccountId`, which must
+        /// match the caller.
+        fn verify_signature(
+            signature: &[u8],
+            message: &[u8],
+            caller: &AccountId,
+        ) -> Result<(), Error> {
+            match signature.len() {
+                64 => {
+                    let mut sig = [0u8; 64];
+                    sig.copy_from_slice(signature);
+                    let mut pubkey = [0u8; 32];
+                    pubkey.copy_from_slice(caller.as_ref());
+                    ink::env::sr25519_verify(&sig, message, &pubkey)
+                        .map_err(|_| Error::InvalidSignature)
+                }
+                65 => {
+                    let mut message_hash = <Blake2x256 as HashOutput>::Type::default();
+                    ink::env::hash_bytes::<Blake2x256>(message, &mut message_hash);
+
+                    let mut sig = [0u8; 65];
+                    sig.copy_from_slice(signature);
+                    let mut recovered_pubkey = [0u8; 33];
+                    ink::env::ecdsa_recover(&sig, &message_hash, &mut recovered_pubkey)
+                        .map_err(|_| Error::RecoveryFailed)?;
+
+                    let mut output = <Blake2x256 as HashOutput>::Type::default();
+                    ink::env::hash_bytes::<Blake2x256>(&recovered_pubkey, &mut output);
+                    let recovered_account = AccountId::from(output);
+
+                    if &recovered_account == caller {
+                        Ok(())
+                    } else {
+                        Err(Error::InvalidSignature)
+                    }
+                }
+                _ => Err(Error::InvalidSignature),
+            }
+        }
     }
 
     #[cfg(test)]
@@ -78,24 +264,43 @@ mod my_contract {
         use super::*;
         use ink::env::test::DefaultAccounts;
 
+        const SR25519_SIGNING_CTX: &[u8] = b"substrate";
+
         fn setup() -> (MyContract, DefaultAccounts<ink::env::DefaultEnvironment>) {
-            let contract = MyContract::new();
+            let contract = MyContract::new(0);
             let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
             (contract, accounts)
         }
 
-        fn sign_message(message: &[u8], _signer: AccountId) -> Vec<u8> {
-            let mut output = <Blake2x256 as HashOutput>::Type::default();
-            ink::env::hash_bytes::<Blake2x256>(message, &mut output);
-            output.to_vec()
+        /// Builds the nonce-bound payload a caller must sign to register.
+        fn expected_message(contract: &MyContract, caller: AccountId) -> Vec<u8> {
+            let mut preimage = Vec::new();
+            preimage.extend_from_slice(&contract.domain_separator);
+            preimage.extend_from_slice(contract.env().account_id().as_ref());
+            preimage.extend_from_slice(caller.as_ref());
+            preimage.extend_from_slice(&contract.nonce_of(caller).to_le_bytes());
+            preimage
+        }
+
+        /// A deterministic sr25519 test keypair, derived from `seed`.
+        fn keypair_from_seed(seed: u8) -> schnorrkel::Keypair {
+            schnorrkel::MiniSecretKey::from_bytes(&[seed; 32])
+                .expect("32-byte seed")
+                .expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
         }
 
-        fn create_invalid_signature() -> Vec<u8> {
-            // Create an invalid signature by using a different message
-            let message = b"Invalid message".to_vec();
-            let mut output = <Blake2x256 as HashOutput>::Type::default();
-            ink::env::hash_bytes::<Blake2x256>(&message, &mut output);
-            output.to_vec()
+        /// The `AccountId` whose bytes are this keypair's sr25519 public key.
+        fn account_id_of(keypair: &schnorrkel::Keypair) -> AccountId {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(&keypair.public.to_bytes());
+            bytes.into()
+        }
+
+        fn sign(keypair: &schnorrkel::Keypair, message: &[u8]) -> Vec<u8> {
+            keypair
+                .sign(schnorrkel::signing_context(SR25519_SIGNING_CTX).bytes(message))
+                .to_bytes()
+                .to_vec()
         }
 
         #[ink::test]
@@ -106,66 +311,189 @@ mod my_contract {
 
         #[ink::test]
         fn registration_with_valid_signature_works() {
-            let (mut contract, accounts) = setup();
-            let message = b"Register me".to_vec();
-            let signature = sign_message(&message, accounts.alice);
+            let (mut contract, _accounts) = setup();
+            let keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&keypair);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(alice);
 
-            // Set the caller to Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let message = expected_message(&contract, alice);
+            let signature = sign(&keypair, &message);
 
             // Register with signature
             assert!(contract.register_with_signature(message, signature).is_ok());
-            assert!(contract.is_registered(accounts.alice));
+            assert!(contract.is_registered(alice));
+            assert_eq!(contract.nonce_of(alice), 1);
         }
 
         #[ink::test]
         fn registration_with_invalid_signature_fails() {
-            let (mut contract, accounts) = setup();
-            let message = b"Register me".to_vec();
-            let signature = create_invalid_signature();
+            let (mut contract, _accounts) = setup();
+            let keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&keypair);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(alice);
 
-            // Set the caller to Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let message = expected_message(&contract, alice);
+            // Valid signature, but from a different keypair than `alice`.
+            let impostor = keypair_from_seed(0x22);
+            let signature = sign(&impostor, &message);
 
             // Registration should fail
             assert!(matches!(
                 contract.register_with_signature(message, signature),
                 Err(Error::InvalidSignature)
             ));
-            assert!(!contract.is_registered(accounts.alice));
+            assert!(!contract.is_registered(alice));
+        }
+
+        #[ink::test]
+        fn registration_emits_registered_event() {
+            let (mut contract, _accounts) = setup();
+            let keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&keypair);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(alice);
+
+            let message = expected_message(&contract, alice);
+            let signature = sign(&keypair, &message);
+            assert!(contract.register_with_signature(message, signature).is_ok());
+
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 1);
+
+            let decoded = <Registered as scale::Decode>::decode(&mut &events[0].data[..])
+                .expect("encountered invalid contract event data buffer");
+            assert_eq!(decoded.account, alice);
+            assert_eq!(decoded.nonce, 0);
+        }
+
+        #[ink::test]
+        fn registration_with_stale_nonce_fails() {
+            let (mut contract, _accounts) = setup();
+            let keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&keypair);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(alice);
+
+            // Sign a message for a nonce other than the account's current one.
+            let mut stale_preimage = Vec::new();
+            stale_preimage.extend_from_slice(&contract.domain_separator);
+            stale_preimage.extend_from_slice(contract.env().account_id().as_ref());
+            stale_preimage.extend_from_slice(alice.as_ref());
+            stale_preimage.extend_from_slice(&1u64.to_le_bytes());
+            let signature = sign(&keypair, &stale_preimage);
+
+            assert!(matches!(
+                contract.register_with_signature(stale_preimage, signature),
+                Err(Error::BadNonce)
+            ));
+            assert!(!contract.is_registered(alice));
         }
 
         #[ink::test]
         fn double_registration_fails() {
-            let (mut contract, accounts) = setup();
-            let message = b"Register me".to_vec();
-            let signature = sign_message(&message, accounts.alice);
+            let (mut contract, _accounts) = setup();
+            let keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&keypair);
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(alice);
 
-            // Set the caller to Alice
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            let message = expected_message(&contract, alice);
+            let signature = sign(&keypair, &message);
 
             // First registration should succeed
-            assert!(contract.register_with_signature(message.clone(), signature.clone()).is_ok());
-            
-            // Second registration should fail
+            assert!(contract.register_with_signature(message, signature).is_ok());
+
+            // Second registration (even with a freshly signed nonce) should fail
+            let message = expected_message(&contract, alice);
+            let signature = sign(&keypair, &message);
             assert!(matches!(
                 contract.register_with_signature(message, signature),
                 Err(Error::AlreadyRegistered)
             ));
         }
+
+        #[ink::test]
+        fn register_batch_reports_per_entry_results() {
+            let (mut contract, _accounts) = setup();
+            let alice_keypair = keypair_from_seed(0x11);
+            let alice = account_id_of(&alice_keypair);
+            let bob_keypair = keypair_from_seed(0x22);
+            let bob = account_id_of(&bob_keypair);
+            let bob_impostor = keypair_from_seed(0x33);
+
+            let alice_message = expected_message(&contract, alice);
+            let alice_signature = sign(&alice_keypair, &alice_message);
+            let bob_message = expected_message(&contract, bob);
+            let bob_bad_signature = sign(&bob_impostor, &bob_message);
+
+            let results = contract.register_batch(vec![
+                (alice, alice_message, alice_signature),
+                (bob, bob_message, bob_bad_signature),
+            ]);
+
+            assert_eq!(results, vec![Ok(()), Err(Error::InvalidSignature)]);
+            assert!(contract.is_registered(alice));
+            assert!(!contract.is_registered(bob));
+        }
+
+        #[ink::test]
+        fn eth_registration_with_valid_signature_works() {
+            use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+
+            let mut contract = MyContract::new(0);
+
+            let secp = Secp256k1::new();
+            let secret_key = SecretKey::from_slice(&[0x42; 32]).unwrap();
+            let public_key = PublicKey::from_secret_key(&secp, &secret_key);
+            let compressed_pubkey = public_key.serialize();
+
+            let mut eth_address = [0u8; 20];
+            ink::env::ecdsa_to_eth_address(&compressed_pubkey, &mut eth_address)
+                .expect("failed to derive eth address");
+
+            let nonce = contract.eth_nonce_of(eth_address);
+            let mut preimage = Vec::new();
+            preimage.extend_from_slice(&contract.domain_separator);
+            preimage.extend_from_slice(contract.env().account_id().as_ref());
+            preimage.extend_from_slice(&eth_address);
+            preimage.extend_from_slice(&nonce.to_le_bytes());
+
+            let mut message_hash = <Blake2x256 as HashOutput>::Type::default();
+            ink::env::hash_bytes::<Blake2x256>(&preimage, &mut message_hash);
+            let msg = Message::from_slice(&message_hash).expect("32-byte message hash");
+            let (recovery_id, sig_bytes) = secp
+                .sign_ecdsa_recoverable(&msg, &secret_key)
+                .serialize_compact();
+            let mut signature = [0u8; 65];
+            signature[..64].copy_from_slice(&sig_bytes);
+            signature[64] = recovery_id.to_i32() as u8;
+
+            assert!(contract
+                .register_with_eth_signature(preimage, signature)
+                .is_ok());
+            assert!(contract.is_eth_registered(eth_address));
+            assert_eq!(contract.eth_nonce_of(eth_address), 1);
+
+            let events = ink::env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(events.len(), 1);
+            let decoded = <EthRegistered as scale::Decode>::decode(&mut &events[0].data[..])
+                .expect("encountered invalid contract event data buffer");
+            assert_eq!(decoded.address, eth_address);
+            assert_eq!(decoded.nonce, 0);
+        }
     }
 
     #[cfg(all(test, feature = "e2e-tests"))]
     mod e2e_tests {
         use super::*;
-        use ink_e2e::ContractsBackend;
+        use ink_e2e::{ContractsBackend, E2EBackend};
 
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-        #[ink_e2e::test]
-        async fn registration_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+        /// Shared test body, generic over the E2E backend. Exercises the
+        /// full signature-verification path end-to-end, including the
+        /// runtime's own sr25519 verification of a signature made by the
+        /// test's `alice` keypair, against both backends below.
+        async fn registration_works<Client: E2EBackend>(mut client: Client) -> E2EResult<()> {
             // Given
-            let mut constructor = MyContractRef::new();
+            let mut constructor = MyContractRef::new(0);
             let contract = client
                 .instantiate("my_contract", &ink_e2e::alice(), &mut constructor)
                 .submit()
@@ -173,8 +501,18 @@ mod my_contract {
                 .expect("instantiate failed");
             let mut call_builder = contract.call_builder::<MyContract>();
 
-            // Create a test message and signature
-            let message = b"Register me".to_vec();
+            // Create the nonce-bound payload Alice must sign, and sign it.
+            let domain_separator_getter = call_builder.domain_separator();
+            let domain_separator = client
+                .call(&ink_e2e::alice(), &domain_separator_getter)
+                .dry_run()
+                .await?
+                .return_value();
+            let mut message = Vec::new();
+            message.extend_from_slice(&domain_separator);
+            message.extend_from_slice(contract.account_id.as_ref());
+            message.extend_from_slice(ink_e2e::alice().account_id().as_ref());
+            message.extend_from_slice(&0u64.to_le_bytes());
             let signature = ink_e2e::alice().sign(&message).to_vec();
 
             // Register
@@ -190,8 +528,27 @@ mod my_contract {
             let is_registered_result = client.call(&ink_e2e::alice(), &is_registered).dry_run().await?;
             assert!(is_registered_result.return_value());
 
+            // The nonce must have advanced, confirming registration actually
+            // applied the signed payload rather than merely returning `Ok`.
+            let nonce_of = call_builder.nonce_of(ink_e2e::alice().account_id());
+            let nonce_of_result = client.call(&ink_e2e::alice(), &nonce_of).dry_run().await?;
+            assert_eq!(nonce_of_result.return_value(), 1);
+
             Ok(())
         }
+
+        /// Runs against a live `pallet-contracts` node.
+        #[ink_e2e::test]
+        async fn registration_works_on_node(client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            registration_works(client).await
+        }
+
+        /// Runs against the in-process DRink! sandbox, so CI doesn't need to
+        /// spin up a full Substrate node for this test.
+        #[ink_e2e::test(backend(runtime_only))]
+        async fn registration_works_on_drink(client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            registration_works(client).await
+        }
     }
 }
 